@@ -125,9 +125,12 @@ uint16_t eeprom_write(EEPROMDescriptor eeprom_descriptor, const void *buf, uint1
     block->dirty = true;
 
     if (block->saveonwrite) {
-        // TODO: add check for write errors
-        count = eeprom_save(eeprom_descriptor);
-        debug("saved %i bytes\n", count);
+        ssize_t saved = eeprom_save(eeprom_descriptor);
+        if (saved != block->size) {
+            debug("eeprom_write: save failed %li != %i\n", saved, block->size);
+            return -1;
+        }
+        debug("saved %li bytes\n", saved);
         block->dirty = false;
     }
 
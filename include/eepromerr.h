@@ -26,7 +26,16 @@ typedef enum {
     // buffer not valid
     BUFFERNOTVALID = -8,
     EEPROMCORRUPTED = -10,
-    EEPROMREADERROR = -11
+    EEPROMREADERROR = -11,
+    // EEPROM header magic not found
+    HEADERMAGICNOTVALID = -12,
+    // EEPROM header crc32 mismatch
+    HEADERCRCNOTVALID = -13,
+    // EEPROM header area is erased to 0x00
+    EEPROMEMPTY = -14,
+    EEPROMWRITEERROR = -15,
+    // EEPROM header area is erased to 0xFF
+    EEPROMEMPTYFF = -16
 } EEPROMError;
 
 #ifdef __cplusplus
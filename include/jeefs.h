@@ -60,14 +60,16 @@ typedef struct {
  * EEPROM_AddFile() - Creates a new file with the given filename and data.
  * EEPROM_DeleteFile() - Deletes the file with the given filename.
  * defragEEPROM() - Compacts the EEPROM by removing gaps caused by deleted files or fragmentation.
- * EEPROM_HeaderCheckConsistency() - Checks the integrity of the file system.
+ * EEPROM_HeaderCheckConsistency() - Checks the integrity of the EEPROM header.
  *
  * Base principles:
  * - EEPROM is divided into files (partitions). Each partition has a name, offset and size.
- * - file name limited to FILE_NAME_LENGTH
+ * - file name limited to FILE_NAME_LENGTH characters, can't be empty
+ * - new file name must be printable ASCII and can't start with a reserved prefix,
+ *   "." and "factory/" by default, see EEPROM_SetReservedNamePrefixes()
  * - files is linked by linked list
  * - files can't be zero size
- * - files can't be fragmented
+ * - files can't be fragmented, nextFileAddress of a file points right after its data or is empty
  * - files on overwrite if size differs are deleted and new file is created
  * - auto defragmentation on every EEPROM_DeleteFile()
  */
@@ -78,37 +80,50 @@ typedef struct {
 int16_t EEPROM_ListFiles(EEPROMDescriptor eeprom_descriptor, char fileList[][FILE_NAME_LENGTH], uint16_t maxFiles);
 
 // Reads the data of the file with the given filename into the buffer.
-// Return: read bytes count, 0 if file not found, <0 if error.
+// Return: read bytes count, FILENOTFOUND if file not found, <0 if error.
+// EEPROMCORRUPTED if file data is out of EEPROM.
 int16_t EEPROM_ReadFile(EEPROMDescriptor eeprom_descriptor, const char *filename, uint8_t *buffer, uint16_t bufferSize);
 
 // Overwrites the data of an existing file with the given filename.
-// Return: written bytes count, 0 if file not found, <0 if error.
+// Return: written bytes count, FILENOTFOUND if file not found, <0 if error.
+// NOTENOUGHSPACE if resized file does not fit, existing file is kept.
+// EEPROMCORRUPTED if file data is out of EEPROM or resize meets a broken file chain.
 int16_t EEPROM_WriteFile(EEPROMDescriptor eeprom_descriptor, const char *filename, const uint8_t *data, uint16_t dataSize);
 
 // Creates a new file with the given filename and data.
 // Return: written bytes count, 0 if file already exists, <0 if error.
+// EEPROMCORRUPTED if file chain is broken, no file is written over it.
 int16_t EEPROM_AddFile(EEPROMDescriptor eeprom_descriptor, const char *filename, const uint8_t *data, uint16_t dataSize);
 
 // Deletes the file with the given filename.
-// Return: 1 if file deleted, 0 if file not found, <0 if error.
+// Return: 1 if file deleted, FILENOTFOUND if file not found, <0 if error.
+// EEPROMCORRUPTED if file data is out of EEPROM or file chain is broken.
 int16_t EEPROM_DeleteFile(EEPROMDescriptor descriptor, const char *filename);
 
 // Compacts the EEPROM by removing gaps caused by deleted files or fragmentation.
 // Return: 1 if EEPROM compacted, 0 if no compaction needed, <0 if error.
 int16_t defragEEPROM(EEPROMDescriptor eeprom_descriptor);
 
-// Checks the integrity of the file system.
-// Return: 1 if file system is consistent, 0 if file system is inconsistent, <0 if error.
+// Checks the integrity of the EEPROM header.
+// Return: 0 if header is consistent, EEPROMEMPTY / EEPROMEMPTYFF if header area is erased to 0x00 / 0xFF,
+// HEADERMAGICNOTVALID / HEADERCRCNOTVALID if inconsistent, <0 if other error.
 int16_t EEPROM_HeaderCheckConsistency(EEPROMDescriptor eeprom_descriptor);
 
-// Set EEPROM_Header
+// Set EEPROM_Header, crc32 is recalculated.
+// Return: 1 if header written, 0 if write error.
 int EEPROM_SetHeader(EEPROMDescriptor eeprom_descriptor, JEEPROMHeader header);
 
+// Sets name prefixes EEPROM_AddFile() refuses to create, prefixes array must stay valid while in use.
+// NULL restores the default prefixes, count 0 reserves nothing.
+void EEPROM_SetReservedNamePrefixes(const char *const prefixes[], uint16_t count);
+
 JEEPROMHeader EEPROM_GetHeader(EEPROMDescriptor eeprom_descriptor);
 
 EEPROMDescriptor EEPROM_OpenEEPROM(const char *pathname, uint16_t eeprom_size);
 int EEPROM_CloseEEPROM(EEPROMDescriptor eeprom_descriptor);
 
+// Writes empty header and clears the file system.
+// Return: 1 if EEPROM formatted, 0 if write error.
 int EEPROM_FormatEEPROM(EEPROMDescriptor ep);
 
 #ifdef __cplusplus
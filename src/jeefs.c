@@ -19,10 +19,21 @@
 static uint32_t calculateCRC32(const uint8_t *data, size_t length);
 static int16_t EEPROM_FindFile(EEPROMDescriptor eeprom_descriptor, const char *filename, JEEFSFileHeader *header, uint16_t *address);
 static uint16_t EEPROM_getNextFileAddress(EEPROMDescriptor eeprom_descriptor, uint16_t currentAddress);
+static bool EEPROM_LinkIsValid(EEPROMDescriptor eeprom_descriptor, uint16_t currentAddress, const JEEFSFileHeader *fileHeader);
+static int16_t EEPROM_CheckFileName(const char *filename);
+static int16_t EEPROM_CheckNewFileName(const char *filename);
+static int16_t EEPROM_AppendFile(EEPROMDescriptor eeprom_descriptor, const char *filename, const uint8_t *data, uint16_t dataSize);
+static int16_t EEPROM_FindAppendSlot(EEPROMDescriptor eeprom_descriptor, uint16_t *lastFileAddress, uint16_t *appendAddress);
+static inline bool EEPROM_FileFits(EEPROMDescriptor eeprom_descriptor, uint32_t address, uint16_t dataSize);
 static inline bool EEPROM_ByteIsEmpty(char var);
 static inline bool EEPROM_WordIsEmpty(uint16_t var);
 static inline bool EEPROM_QWordIsEmpty(uint32_t var);
 
+// Name prefixes reserved for system files, EEPROM_AddFile() refuses to create them
+static const char *const defaultReservedNamePrefixes[] = {".", "factory/"};
+static const char *const *reservedNamePrefixes = defaultReservedNamePrefixes;
+static uint16_t reservedNamePrefixesCount = sizeof(defaultReservedNamePrefixes) / sizeof(defaultReservedNamePrefixes[0]);
+
 
 /*
  * JEEFS functions
@@ -68,15 +79,19 @@ int16_t EEPROM_ListFiles(EEPROMDescriptor eeprom_descriptor, char fileList[][FIL
 }
 
 int16_t EEPROM_ReadFile(EEPROMDescriptor eeprom_descriptor, const char *filename, uint8_t *buffer, uint16_t bufferSize) {
-    if (!filename || strlen(filename) > FILE_NAME_LENGTH)
-        return FILENAMENOTVALID;
+    int16_t nameCheck = EEPROM_CheckFileName(filename);
+    if (nameCheck < 0)
+        return nameCheck;
 
     if (!buffer || bufferSize == 0)
         return BUFFERNOTVALID;
 
     JEEFSFileHeader fileHeader;
     uint16_t fileAddress;
-    if (EEPROM_FindFile(eeprom_descriptor, filename, &fileHeader, &fileAddress) != 1) {
+    int16_t found = EEPROM_FindFile(eeprom_descriptor, filename, &fileHeader, &fileAddress);
+    if (found < 0)
+        return found;
+    if (found != 1) {
         return FILENOTFOUND; // File not found
     }
     if (fileHeader.dataSize > bufferSize) {
@@ -84,41 +99,64 @@ int16_t EEPROM_ReadFile(EEPROMDescriptor eeprom_descriptor, const char *filename
     }
 
     ssize_t readSize = eeprom_read(eeprom_descriptor, buffer, fileHeader.dataSize, fileAddress + sizeof(JEEFSFileHeader));
-    return readSize > 0 ? readSize : -1; // Return read bytes or 0 on error
+    return readSize == fileHeader.dataSize ? readSize : EEPROMREADERROR; // Return read bytes or read error
 }
 
 
 
 int16_t EEPROM_WriteFile(EEPROMDescriptor eeprom_descriptor, const char *filename, const uint8_t *data, uint16_t dataSize) {
-    if (!filename || strlen(filename) > FILE_NAME_LENGTH)
-        return FILENAMENOTVALID;
+    int16_t nameCheck = EEPROM_CheckFileName(filename);
+    if (nameCheck < 0)
+        return nameCheck;
 
     if (!data || dataSize == 0)
         return BUFFERNOTVALID;
 
     JEEFSFileHeader fileHeader;
     uint16_t fileAddress;
-    if (EEPROM_FindFile(eeprom_descriptor, filename, &fileHeader, &fileAddress) != 1)
+    int16_t found = EEPROM_FindFile(eeprom_descriptor, filename, &fileHeader, &fileAddress);
+    if (found < 0)
+        return found;
+    if (found != 1)
         // File not found
         return FILENOTFOUND;
 
     // File found
     if (fileHeader.dataSize != dataSize) {
+        // Check that resized file fits before deleting the old one
+        uint16_t lastFileAddress, appendAddress;
+        int16_t err = EEPROM_FindAppendSlot(eeprom_descriptor, &lastFileAddress, &appendAddress);
+        if (err < 0)
+            return err;
+        // Deleting the old file moves the append slot down if the file is before it
+        uint32_t newAddress = appendAddress;
+        if (fileAddress < appendAddress)
+            newAddress -= sizeof(JEEFSFileHeader) + fileHeader.dataSize;
+        if (!EEPROM_FileFits(eeprom_descriptor, newAddress, dataSize)) {
+            debug("EEPROM_WriteFile: not enough space %s %u -> %u append address: %u eeprom_size: %lu\n",
+                  filename, fileHeader.dataSize, dataSize, newAddress, eeprom_descriptor.eeprom_size);
+            return NOTENOUGHSPACE;
+        }
         // Different size, delete, defrag, and create new file
-        EEPROM_DeleteFile(eeprom_descriptor, filename);
+        err = EEPROM_DeleteFile(eeprom_descriptor, filename);
+        if (err < 0)
+            return err;
         // not needed, already in EEPROM_DeleteFile
         // defragEEPROM(eeprom_descriptor);
-        return EEPROM_AddFile(eeprom_descriptor, filename, data, dataSize);
+        // Existing file keeps its name, so new file name rules of EEPROM_AddFile are not applied
+        return EEPROM_AppendFile(eeprom_descriptor, filename, data, dataSize);
     }
 
     // Overwrite the file content
     if (eeprom_write(eeprom_descriptor, data, dataSize, fileAddress + sizeof(JEEFSFileHeader)) != dataSize) {
-        return 0;  // Write error
+        return EEPROMWRITEERROR;
     }
 
     // Update the CRC
     fileHeader.crc32 = calculateCRC32(data, dataSize);
-    eeprom_write(eeprom_descriptor, &fileHeader, sizeof(JEEFSFileHeader), fileAddress);
+    if (eeprom_write(eeprom_descriptor, &fileHeader, sizeof(JEEFSFileHeader), fileAddress) != sizeof(JEEFSFileHeader)) {
+        return EEPROMWRITEERROR;
+    }
 
     return dataSize;
 }
@@ -141,9 +179,10 @@ int16_t EEPROM_AddFile(EEPROMDescriptor eeprom_descriptor, const char *filename,
      *
      */
 
-    if (!filename || strlen(filename) > FILE_NAME_LENGTH) {
-        debug("EEPROM_AddFile: %s %s %u\n", "FILENAMENOTVALID", filename, dataSize);
-        return FILENAMENOTVALID;
+    int16_t nameCheck = EEPROM_CheckNewFileName(filename);
+    if (nameCheck < 0) {
+        debug("EEPROM_AddFile: filename not valid %i %s %u\n", nameCheck, filename ? filename : "(null)", dataSize);
+        return nameCheck;
     }
 
     if (!data || dataSize == 0) {
@@ -152,79 +191,54 @@ int16_t EEPROM_AddFile(EEPROMDescriptor eeprom_descriptor, const char *filename,
     }
 
 
-    uint16_t currentAddress = sizeof(JEEPROMHeader); // Starting after the EEPROM header
-    uint16_t previousAddress;
-    JEEFSFileHeader currentFileHeader;
-
-    if (EEPROM_FindFile(eeprom_descriptor, filename, &currentFileHeader, &previousAddress) == 1) {
+    int16_t found = EEPROM_FindFile(eeprom_descriptor, filename, NULL, NULL);
+    if (found == 1) {
         debug("EEPROM_AddFile: file already exists: %s\n", filename);
         // TODO: Update file or return error?
         return 0; // File already exists
     }
-    previousAddress = 0;
-    ssize_t readSize;
+    if (found < 0) {
+        debug("EEPROM_AddFile: file lookup error %s %i\n", filename, found);
+        return found;
+    }
 
     debug("EEPROM_AddFile: file %s not found. add new\n", filename);
 
-    while (!EEPROM_WordIsEmpty(currentAddress) && currentAddress < eeprom_descriptor.eeprom_size - sizeof(JEEFSFileHeader)) {
-
-        readSize = eeprom_read(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), currentAddress);
-
-        // If the current slot is empty (first time adding a file or a deleted slot) or there's an error in reading
-        if (readSize != sizeof(JEEFSFileHeader)) {
-            debug("EEPROM_AddFile: read eeprom error %s %li != %li\n", filename, readSize, sizeof(JEEFSFileHeader));
-            return EEPROMREADERROR; // Read error
-        }
-
-        if (EEPROM_ByteIsEmpty(currentFileHeader.name[0])
-        || EEPROM_WordIsEmpty(currentFileHeader.dataSize)
-        ||
-            (   !EEPROM_WordIsEmpty(currentFileHeader.nextFileAddress)
-                && currentFileHeader.nextFileAddress != currentFileHeader.dataSize + sizeof (JEEFSFileHeader) + currentAddress
-                )
-        ) {
-            break; // Found empty slot or read error occurred
-        }
-
-        // TODO : read and check crc32 of data
-        // if (currentFileHeader.crc32 == calculateCRC32(data, dataSize))
+    return EEPROM_AppendFile(eeprom_descriptor, filename, data, dataSize);
+}
 
-        previousAddress = currentAddress;
-        // TODO: select corruption of all header or only nextFileAddress?
-        /*if(currentFileHeader.nextFileAddress != currentFileHeader.dataSize + sizeof (JEEFSFileHeader)) {
-            // TODO: fix corruption, maybe restore or return error?
-            // return EEPROMCORRUPTED;
-            currentFileHeader.nextFileAddress = 0;
-            break; // Found empty slot or read error occurred
-        }*/
-        currentAddress = currentFileHeader.nextFileAddress; // Move to next file
+// Appends new file to the end of the file chain, filename and data must be already checked
+int16_t EEPROM_AppendFile(EEPROMDescriptor eeprom_descriptor, const char *filename, const uint8_t *data, uint16_t dataSize) {
+    uint16_t previousAddress;
+    uint16_t currentAddress;
+    JEEFSFileHeader currentFileHeader;
+    ssize_t readSize;
 
+    int16_t err = EEPROM_FindAppendSlot(eeprom_descriptor, &previousAddress, &currentAddress);
+    if (err < 0) {
+        debug("EEPROM_AppendFile: find append slot error %s %i\n", filename, err);
+        return err;
     }
 
-    // Exit from loop in search of empty space
-    // assume that currentAddress is empty or corrupted
-    // assume that previousAddress is valid or zero
-    // so if previousAddress is zero then currentAddress is first file and do not update previous file header
-    // if previousAddress is not zero then currentAddress is not first file and update previous file header
-    // write code below:
-
-    if (previousAddress) {
-        // TODO: check on read error
-        readSize = eeprom_read(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), previousAddress);
-        currentFileHeader.nextFileAddress = previousAddress + sizeof(JEEFSFileHeader) + currentFileHeader.dataSize;
-        currentAddress = currentFileHeader.nextFileAddress;
-    } else
-        currentAddress = sizeof(JEEPROMHeader);
-
     // Check if there's enough space to write the new file
-    if (currentAddress + sizeof(currentFileHeader) + dataSize >= eeprom_descriptor.eeprom_size) {
-        debug("EEPROM_AddFile: not enough space %s %u %u eeprom_size: %lu\n", filename, currentAddress, dataSize, eeprom_descriptor.eeprom_size);
+    if (!EEPROM_FileFits(eeprom_descriptor, currentAddress, dataSize)) {
+        debug("EEPROM_AppendFile: not enough space %s %u %u eeprom_size: %lu\n", filename, currentAddress, dataSize, eeprom_descriptor.eeprom_size);
         return NOTENOUGHSPACE;  // Not enough space
     }
 
-    // TODO: check on write error
-    if (previousAddress)
-        readSize = eeprom_write(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), previousAddress);
+    if (previousAddress) {
+        // Link previous file to the new one
+        readSize = eeprom_read(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), previousAddress);
+        if (readSize != sizeof(JEEFSFileHeader)) {
+            debug("EEPROM_AppendFile: read error %s %u\n", filename, previousAddress);
+            return EEPROMREADERROR;
+        }
+        currentFileHeader.nextFileAddress = currentAddress;
+        if (eeprom_write(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), previousAddress) != sizeof(JEEFSFileHeader)) {
+            debug("EEPROM_AppendFile: write error %s %u\n", filename, previousAddress);
+            return EEPROMWRITEERROR;
+        }
+    }
 
 
     // Prepare and write the new file header
@@ -239,17 +253,17 @@ int16_t EEPROM_AddFile(EEPROMDescriptor eeprom_descriptor, const char *filename,
     ssize_t writeSize;
     writeSize = eeprom_write(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), currentAddress);
     if (writeSize != sizeof(JEEFSFileHeader)) {
-        debug("EEPROM_AddFile: write lastFile header eeprom error %s %li != %li\n", filename, writeSize, sizeof(JEEFSFileHeader));
-        return -1; // Write error
+        debug("EEPROM_AppendFile: write lastFile header eeprom error %s %li != %li\n", filename, writeSize, sizeof(JEEFSFileHeader));
+        return EEPROMWRITEERROR;
     }
 
     // Write the file data
     writeSize = eeprom_write(eeprom_descriptor, data, dataSize, currentAddress + sizeof(JEEFSFileHeader));
     if (writeSize != dataSize) {
-        debug("EEPROM_AddFile: write data eeprom error %s %lu != %i\n", filename, writeSize, dataSize);
-        return -1; // Write error
+        debug("EEPROM_AppendFile: write data eeprom error %s %lu != %i\n", filename, writeSize, dataSize);
+        return EEPROMWRITEERROR;
     }
-    debug("EEPROM_AddFile: write data eeprom ok %s %li seek:%i\n", filename, writeSize, currentAddress);
+    debug("EEPROM_AppendFile: write data eeprom ok %s %li seek:%i\n", filename, writeSize, currentAddress);
 
     return (int16_t)(dataSize%INT16_MAX); // Return number of data bytes written
 }
@@ -257,19 +271,31 @@ int16_t EEPROM_AddFile(EEPROMDescriptor eeprom_descriptor, const char *filename,
 
 
 int16_t EEPROM_DeleteFile(EEPROMDescriptor descriptor, const char *filename) {
-    if (!filename || strlen(filename) > FILE_NAME_LENGTH)
-        return FILENAMENOTVALID;
+    int16_t nameCheck = EEPROM_CheckFileName(filename);
+    if (nameCheck < 0)
+        return nameCheck;
 
     JEEFSFileHeader header;
     uint16_t address;
 
     int found = EEPROM_FindFile(descriptor, filename, &header, &address);
-    if (found <= 0) {
-        return FILENOTFOUND;  // File not found or error
+    if (found < 0) {
+        return found;
+    }
+    if (found == 0) {
+        return FILENOTFOUND;  // File not found
+    }
+
+    // Compaction moves all data after the file, don't move files behind a broken link
+    uint16_t lastFileAddress, appendAddress;
+    int16_t err = EEPROM_FindAppendSlot(descriptor, &lastFileAddress, &appendAddress);
+    if (err < 0) {
+        return err;
     }
 
     // The address after the file we're deleting
     uint16_t nextAddress = address + sizeof(JEEFSFileHeader) + header.dataSize;
+    bool wasLast = EEPROM_WordIsEmpty(header.nextFileAddress);
 
     // Move all subsequent files up to fill the space of the deleted file
     uint16_t shiftSize = sizeof(JEEFSFileHeader) + header.dataSize;
@@ -278,29 +304,65 @@ int16_t EEPROM_DeleteFile(EEPROMDescriptor descriptor, const char *filename) {
     ssize_t bytesRead;
 
     while (readAddress < descriptor.eeprom_size) {
-        bytesRead = eeprom_read(descriptor, buffer, shiftSize, readAddress);
-        if (bytesRead <= 0) {
-            // We've hit the end of our valid data or encountered an error
-            break;
+        uint16_t chunkSize = shiftSize;
+        if (readAddress + chunkSize > descriptor.eeprom_size)
+            chunkSize = descriptor.eeprom_size - readAddress;
+        bytesRead = eeprom_read(descriptor, buffer, chunkSize, readAddress);
+        if (bytesRead != chunkSize) {
+            return EEPROMREADERROR;
         }
 
-        eeprom_write(descriptor, buffer, bytesRead, readAddress - shiftSize);
+        if (eeprom_write(descriptor, buffer, bytesRead, readAddress - shiftSize) != bytesRead) {
+            return EEPROMWRITEERROR;
+        }
 
         readAddress += bytesRead;
     }
 
     // Clear out the remaining space
-    uint8_t clearByte = EEPROM_EMPTYBYTE;
-    for (uint16_t i = 0; i < shiftSize && (readAddress - i) < descriptor.eeprom_size; i++) {
-        eeprom_write(descriptor, &clearByte, 1, readAddress - i - 1);
+    memset(buffer, EEPROM_EMPTYBYTE, shiftSize);
+    if (eeprom_write(descriptor, buffer, shiftSize, readAddress - shiftSize) != shiftSize) {
+        return EEPROMWRITEERROR;
+    }
+
+    // Moved files keep absolute links, shift them by the same size.
+    // If the deleted file was the last one, terminate the chain at previous file.
+    uint16_t currentAddress = sizeof(JEEPROMHeader);
+    while (eeprom_read(descriptor, &header, sizeof(JEEFSFileHeader), currentAddress) == sizeof(JEEFSFileHeader)
+           && !EEPROM_ByteIsEmpty(header.name[0])
+           && !EEPROM_WordIsEmpty(header.nextFileAddress)) {
+        uint16_t linkAddress = header.nextFileAddress;
+        if (currentAddress >= address) {
+            if (linkAddress < address + shiftSize) {
+                debug("EEPROM_DeleteFile: broken link %u -> %u\n", currentAddress, linkAddress);
+                return EEPROMCORRUPTED;
+            }
+            linkAddress -= shiftSize;
+        } else if (wasLast && linkAddress == address)
+            linkAddress = 0;
+
+        if (linkAddress != header.nextFileAddress) {
+            header.nextFileAddress = linkAddress;
+            if (!EEPROM_LinkIsValid(descriptor, currentAddress, &header)) {
+                debug("EEPROM_DeleteFile: broken link %u -> %u\n", currentAddress, linkAddress);
+                return EEPROMCORRUPTED;
+            }
+            if (eeprom_write(descriptor, &header, sizeof(JEEFSFileHeader), currentAddress) != sizeof(JEEFSFileHeader)) {
+                return EEPROMWRITEERROR;
+            }
+        }
+        if (linkAddress <= currentAddress)
+            break; // End of file list or broken link
+        currentAddress = linkAddress;
     }
 
     return 1;  // Successfully deleted
 }
 
 int16_t EEPROM_FindFile(EEPROMDescriptor eeprom_descriptor, const char *filename, JEEFSFileHeader *header, uint16_t *address) {
-    if (!filename || strlen(filename) > FILE_NAME_LENGTH)
-        return FILENAMENOTVALID;
+    int16_t nameCheck = EEPROM_CheckFileName(filename);
+    if (nameCheck < 0)
+        return nameCheck;
 
     uint16_t currentAddress = sizeof(JEEPROMHeader); // Starting after the EEPROM header
 
@@ -308,11 +370,15 @@ int16_t EEPROM_FindFile(EEPROMDescriptor eeprom_descriptor, const char *filename
         JEEFSFileHeader fileHeader;
         ssize_t readSize = eeprom_read(eeprom_descriptor, &fileHeader, sizeof(JEEFSFileHeader), currentAddress);
         if (readSize != sizeof(JEEFSFileHeader)) {
-            return -1; // File not found or read error
+            return EEPROMREADERROR;
         }
 
         if (strncmp(fileHeader.name, filename, FILE_NAME_LENGTH) == 0) {
-            // Found the file
+            // Found the file, its data must fit in eeprom
+            if ((uint32_t) currentAddress + sizeof(JEEFSFileHeader) + fileHeader.dataSize > eeprom_descriptor.eeprom_size) {
+                debug("EEPROM_FindFile: file %s data out of eeprom %u + %u\n", filename, currentAddress, fileHeader.dataSize);
+                return EEPROMCORRUPTED;
+            }
             if (header) {
                 memcpy(header, &fileHeader, sizeof(JEEFSFileHeader));
             }
@@ -331,6 +397,48 @@ int16_t EEPROM_FindFile(EEPROMDescriptor eeprom_descriptor, const char *filename
     return 0; // File not found
 }
 
+// Filename must be 1..FILE_NAME_LENGTH characters
+int16_t EEPROM_CheckFileName(const char *filename) {
+    if (!filename)
+        return FILENAMENOTVALID;
+
+    size_t length = strlen(filename);
+    if (length == 0)
+        return FILENAMETOOSHORT;
+    if (length > FILE_NAME_LENGTH)
+        return FILENAMETOOLONG;
+
+    return 0;
+}
+
+// New filename must also be printable ASCII and not start with a reserved prefix
+int16_t EEPROM_CheckNewFileName(const char *filename) {
+    int16_t nameCheck = EEPROM_CheckFileName(filename);
+    if (nameCheck < 0)
+        return nameCheck;
+
+    for (size_t i = 0; filename[i]; i++) {
+        if (filename[i] < ' ' || filename[i] > '~')
+            return FILENAMENOTVALID;
+    }
+
+    for (uint16_t i = 0; i < reservedNamePrefixesCount; i++) {
+        if (strncmp(filename, reservedNamePrefixes[i], strlen(reservedNamePrefixes[i])) == 0)
+            return FILENAMENOTVALID;
+    }
+    return 0;
+}
+
+void EEPROM_SetReservedNamePrefixes(const char *const prefixes[], uint16_t count) {
+    if (!prefixes) {
+        reservedNamePrefixes = defaultReservedNamePrefixes;
+        reservedNamePrefixesCount = sizeof(defaultReservedNamePrefixes) / sizeof(defaultReservedNamePrefixes[0]);
+        return;
+    }
+    reservedNamePrefixes = prefixes;
+    reservedNamePrefixesCount = count;
+}
+
 inline uint32_t calculateCRC32(const uint8_t *data, size_t length) {
     return crc32(0L, data, length);
 }
@@ -340,29 +448,128 @@ uint16_t EEPROM_getNextFileAddress(EEPROMDescriptor eeprom_descriptor, uint16_t
     if (eeprom_read(eeprom_descriptor, &fileHeader, sizeof(JEEFSFileHeader), currentAddress) != sizeof(JEEFSFileHeader)) {
         return 0; // Error reading header
     }
+    if (EEPROM_WordIsEmpty(fileHeader.nextFileAddress)) {
+        return 0; // End of file list
+    }
+    if (!EEPROM_LinkIsValid(eeprom_descriptor, currentAddress, &fileHeader)) {
+        debug("EEPROM_getNextFileAddress: broken link %u -> %u\n", currentAddress, fileHeader.nextFileAddress);
+        return 0;
+    }
     return fileHeader.nextFileAddress;
 }
 
+// Files can't be fragmented: link is either empty (end of file list) or points right after
+// the current file data and leaves room for next header, so every walk stops on cycles and gaps
+bool EEPROM_LinkIsValid(EEPROMDescriptor eeprom_descriptor, uint16_t currentAddress, const JEEFSFileHeader *fileHeader) {
+    if (EEPROM_WordIsEmpty(fileHeader->nextFileAddress))
+        return true;
+    return fileHeader->nextFileAddress == (uint32_t) currentAddress + sizeof(JEEFSFileHeader) + fileHeader->dataSize
+           && fileHeader->nextFileAddress + sizeof(JEEFSFileHeader) <= eeprom_descriptor.eeprom_size;
+}
+
+// Finds where new file is appended: right after the last file of the chain.
+// lastFileAddress is 0 if there are no files. Returns EEPROMCORRUPTED if the chain has a broken link.
+int16_t EEPROM_FindAppendSlot(EEPROMDescriptor eeprom_descriptor, uint16_t *lastFileAddress, uint16_t *appendAddress) {
+    uint16_t currentAddress = sizeof(JEEPROMHeader); // Starting after the EEPROM header
+    uint16_t previousAddress = 0;
+    JEEFSFileHeader currentFileHeader;
+    ssize_t readSize;
+
+    while (!EEPROM_WordIsEmpty(currentAddress) && currentAddress < eeprom_descriptor.eeprom_size - sizeof(JEEFSFileHeader)) {
+
+        readSize = eeprom_read(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), currentAddress);
+
+        // If the current slot is empty (first time adding a file or a deleted slot) or there's an error in reading
+        if (readSize != sizeof(JEEFSFileHeader)) {
+            debug("EEPROM_FindAppendSlot: read eeprom error %li != %li\n", readSize, sizeof(JEEFSFileHeader));
+            return EEPROMREADERROR; // Read error
+        }
+
+        if (EEPROM_ByteIsEmpty(currentFileHeader.name[0])
+        || EEPROM_WordIsEmpty(currentFileHeader.dataSize)
+        ) {
+            break; // Found empty slot
+        }
+
+        if (!EEPROM_LinkIsValid(eeprom_descriptor, currentAddress, &currentFileHeader)) {
+            // Files behind the broken link may still hold data, don't append over them
+            debug("EEPROM_FindAppendSlot: broken link %u -> %u\n", currentAddress, currentFileHeader.nextFileAddress);
+            return EEPROMCORRUPTED;
+        }
+
+        // TODO : read and check crc32 of data
+        // if (currentFileHeader.crc32 == calculateCRC32(data, dataSize))
+
+        previousAddress = currentAddress;
+        currentAddress = currentFileHeader.nextFileAddress; // Move to next file
+
+    }
+
+    // Exit from loop in search of empty space
+    // assume that currentAddress is empty or corrupted
+    // assume that previousAddress is valid or zero
+    // so if previousAddress is zero then new file is the first one
+    // if previousAddress is not zero then new file goes right after previous file data
+
+    if (previousAddress) {
+        readSize = eeprom_read(eeprom_descriptor, &currentFileHeader, sizeof(JEEFSFileHeader), previousAddress);
+        if (readSize != sizeof(JEEFSFileHeader)) {
+            debug("EEPROM_FindAppendSlot: read eeprom error %li != %li\n", readSize, sizeof(JEEFSFileHeader));
+            return EEPROMREADERROR; // Read error
+        }
+        currentAddress = previousAddress + sizeof(JEEFSFileHeader) + currentFileHeader.dataSize;
+    } else
+        currentAddress = sizeof(JEEPROMHeader);
+
+    *lastFileAddress = previousAddress;
+    *appendAddress = currentAddress;
+    return 0;
+}
+
+inline bool EEPROM_FileFits(EEPROMDescriptor eeprom_descriptor, uint32_t address, uint16_t dataSize) {
+    return address + sizeof(JEEFSFileHeader) + dataSize < eeprom_descriptor.eeprom_size;
+}
 
 int EEPROM_SetHeader(EEPROMDescriptor eeprom_descriptor, JEEPROMHeader header) {
     header.crc32 = calculateCRC32((uint8_t *) &header, sizeof(JEEPROMHeader) - sizeof(header.crc32));
-    return eeprom_write(eeprom_descriptor, &header, sizeof(JEEPROMHeader), 0) == sizeof(JEEPROMHeader);
+    if (eeprom_write(eeprom_descriptor, &header, sizeof(JEEPROMHeader), 0) != sizeof(JEEPROMHeader)) {
+        debug("EEPROM_SetHeader: write header error\n");
+        return 0;
+    }
+    return 1;
 }
 
 int16_t EEPROM_HeaderCheckConsistency(EEPROMDescriptor eeprom_descriptor)
 {
-    JEEPROMHeader header = EEPROM_GetHeader(eeprom_descriptor);
+    JEEPROMHeader header;
+    if (eeprom_read(eeprom_descriptor, &header, sizeof(JEEPROMHeader), 0) != sizeof(JEEPROMHeader)) {
+        debug("EEPROM_HeaderCheckConsistency: read header error\n");
+        return EEPROMREADERROR;
+    }
     uint32_t crc32old = header.crc32;
     //header.crc32 = 0;
     // check header with magic "JetHome" in begin:
     if (strncmp(header.magic, "JetHome", 7) != 0) {
+        // distinguish erased EEPROM from foreign data:
+        // header and first file header must be filled with the same empty byte
+        uint8_t raw[sizeof(JEEPROMHeader) + sizeof(JEEFSFileHeader)];
+        bool erased = eeprom_read(eeprom_descriptor, raw, sizeof(raw), 0) == sizeof(raw)
+                      && EEPROM_ByteIsEmpty((char) raw[0]);
+        for (size_t i = 1; erased && i < sizeof(raw); i++) {
+            if (raw[i] != raw[0])
+                erased = false;
+        }
+        if (erased) {
+            debug("EEPROM_HeaderCheckConsistency: eeprom is empty 0x%02x\n", raw[0]);
+            return raw[0] == 0xFF ? EEPROMEMPTYFF : EEPROMEMPTY;
+        }
         debug("EEPROM_HeaderCheckConsistency: magic error %.8s\n", header.magic);
-        return -1;
+        return HEADERMAGICNOTVALID;
     }
     uint32_t crc32_calc = calculateCRC32((uint8_t *) &header, sizeof(JEEPROMHeader) - sizeof((&header)->crc32));
     if (crc32_calc != crc32old) {
         debug("EEPROM_HeaderCheckConsistency: crc32 error %u != %u\n", crc32_calc, crc32old);
-        return -1;
+        return HEADERCRCNOTVALID;
     }
     return 0;
 }
@@ -377,7 +584,10 @@ int EEPROM_FormatEEPROM(EEPROMDescriptor ep){
     header->crc32 = calculateCRC32((uint8_t *)header, sizeof(JEEPROMHeader) - sizeof(header->crc32));
     debug("EEPROM_FormatEEPROM: crc32: %x buffer size:%lu header size: %lu\n", header->crc32, ep.eeprom_size, sizeof(*header));
     /*EEPROM_SetHeader(ep, header);*/
-    eeprom_write(ep, &buffer, ep.eeprom_size, 0);
+    if (eeprom_write(ep, &buffer, ep.eeprom_size, 0) != ep.eeprom_size) {
+        debug("EEPROM_FormatEEPROM: write eeprom error\n");
+        return 0;
+    }
     return 1;
 }
 
@@ -56,3 +56,22 @@ int delete_files(const char *path, const char *basename, int num_files) {
 
     return 0;
 }
+
+void write_raw_file(EEPROMDescriptor ep, uint16_t address, const char *name, const uint8_t *data, uint16_t dataSize, uint16_t nextFileAddress) {
+    JEEFSFileHeader fileHeader;
+    memset(&fileHeader, 0, sizeof(JEEFSFileHeader));
+    strncpy(fileHeader.name, name, FILE_NAME_LENGTH);
+    fileHeader.dataSize = dataSize;
+    fileHeader.nextFileAddress = nextFileAddress;
+    eeprom_write(ep, &fileHeader, sizeof(JEEFSFileHeader), address);
+    if (data)
+        eeprom_write(ep, data, dataSize, address + sizeof(JEEFSFileHeader));
+}
+
+int compare_file(EEPROMDescriptor ep, const char *name, const uint8_t *data, uint16_t dataSize) {
+    uint8_t filedata[ep.eeprom_size];
+    memset(filedata, 0, ep.eeprom_size);
+    int err = EEPROM_ReadFile(ep, name, filedata, ep.eeprom_size);
+    printf("Read file %s: %i\n", name, err);
+    return err == dataSize && memcmp(filedata, data, dataSize) == 0;
+}
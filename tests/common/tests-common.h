@@ -7,6 +7,8 @@
 #ifndef JEEFS_TESTS_COMMON_H
 #define JEEFS_TESTS_COMMON_H
 
+#include "jeefs.h"
+
 #ifndef TEST_DIR
 #define TEST_DIR "/tmp"
 #endif
@@ -54,6 +56,27 @@ int generate_files(const char *path, const char *basename, int num_files, int ma
  */
  int delete_files(const char *path, const char *basename, int num_files);
 
+/**
+ * @brief Write file header and data directly to eeprom, bypassing EEPROM_AddFile checks
+ * @param ep
+ * @param address
+ * @param name
+ * @param data file data, NULL to write header only
+ * @param dataSize
+ * @param nextFileAddress
+ */
+void write_raw_file(EEPROMDescriptor ep, uint16_t address, const char *name, const uint8_t *data, uint16_t dataSize, uint16_t nextFileAddress);
+
+/**
+ * @brief Read file from eeprom and compare it with expected data
+ * @param ep
+ * @param name
+ * @param data
+ * @param dataSize
+ * @return 1 if file content matches, 0 otherwise
+ */
+int compare_file(EEPROMDescriptor ep, const char *name, const uint8_t *data, uint16_t dataSize);
+
 static char *test_files[] = {
          "Hello, file 0!wrbqhdrokyidsdrmwrsylbfacyedgxplrlnppfkokcqnnuwsmbucjismktxxvrbjtsfzfmfdrsfbnvhfsqwqaeczfklojpprxizxchkccedofddfgxqkydcdwtcoodqvcgpombaunyxzggptwlsduumqdueoyhahdmxdylnquwgljuwixbmneadmdaxohqmhvhovuopylemoezicspgbizruxmufkroziobpelpajaqdnwtjmppaxsughiqbjjvdsybemsqogxmeyzjgboffsdxisehczfirqnzqsbpysnpktdbobqwvfjjdngivgivcabepvghjebiuzzbuzasqquiwvdwvbrzgjfxtunssluuflbnkpalcijdszyeufcfoemjwgwkbehgcahsemphruydrbseyaobtnmwjsxkdrxrcdnovpxpdrfrqfgnrexnufpcgwxuyfcqnbmitclfzermevqdjqugnaqrjoxpwjbssfjexxnflwwnbjkmouhvgwjqxicoridhrschlehtmawwqsenfvwvjfzxcdnqjaokxgiecklogqvbsvvenqmrirmlbrkhynmodycguihexjroujuhdpzsygyqjhrryuzrnkhlfkebdpfijxhncmcoqndmzbnmphdtsqeeguismrgwrtadupzynr",
          "Hello, file 1!jncmkzdszodupnukumnfmscjaxrdyqczbvqqjtvnbaizwatzpmbjnvehzcpnumpljnewygfnxmsapdzmqxvqnblzgzmpnjlywxtonbiklskfcnmqlefnmuqoscoeyhgwoyvodfqwbpijmwplvcabbwbetwnnyvdxuqsabpthormfrckvbfhohnypbtrabdewpalhsttfslzuqsydtmrzqeehkkfpcvzsdcbiweyzftoksxgoxissfqjncdrluezmnunxlygluadyvaaslvcvimiwqskwxanniaebubqgxcrnxqlophoiammxvsafyncermxsjoegpqiqwrgrkhcihikmpsdgnxzswtcmawnnpdpulxkvrguerglkawbrmaieqvfhccjrbbgslquvevthtmxqvfpxwwjblzdcsdwqpuahgnaeoroqkxpzqlmobjrmxcbtovkjpsqxkuzoojicxbtmjnpvugaskfgtiqjllzmcmcedxlumaghfuvaricrfqwuqoesqrykhnjsxeyfuoqmytypaslzvedlgzjdrhdydndaswsxjwmfaxnjoimrrexlcfkvxqscxzwqiyapuuftoqnqixlsoadskgfxndlqmyetjikosqxtdqskvhawualdkdiyyeuzytjixmyokvsiijcytykj",
@@ -59,7 +59,23 @@ void test0(void) {
     printf("Check EEPROM_header: %i\n", EEPROM_consistency);
 
     assert("Check EEPROM_header non consistency on empty file" && EEPROM_consistency != 0);
-    EEPROM_FormatEEPROM(ep);
+    assert("Check EEPROM_header reports empty file" && EEPROM_consistency == EEPROMEMPTY);
+
+    uint8_t erased[sizeof(JEEPROMHeader) + sizeof(JEEFSFileHeader)];
+    memset(erased, 0xFF, sizeof(erased));
+    assert(eeprom_write(ep, erased, sizeof(erased), 0) == sizeof(erased));
+    EEPROM_consistency = EEPROM_HeaderCheckConsistency(ep);
+    printf("Check EEPROM_header erased to 0xFF: %i\n", EEPROM_consistency);
+    assert("Check EEPROM_header reports 0xFF erased file" && EEPROM_consistency == EEPROMEMPTYFF);
+
+    // Erased header followed by file data is not empty EEPROM
+    write_raw_file(ep, sizeof(JEEPROMHeader), TEST_FILENAME, NULL, 16, 0);
+    EEPROM_consistency = EEPROM_HeaderCheckConsistency(ep);
+    printf("Check EEPROM_header with file data: %i\n", EEPROM_consistency);
+    assert("Check EEPROM_header reports foreign data" && EEPROM_consistency == HEADERMAGICNOTVALID);
+
+    int err = EEPROM_FormatEEPROM(ep);
+    assert("Check EEPROM_FormatEEPROM result" && err == 1);
     EEPROM_CloseEEPROM(ep);
 
     ep = EEPROM_OpenEEPROM(TEST_FULL_EEPROM_FILENAME, 0);
@@ -77,6 +77,16 @@ void test1() {
     }
     assert("Check EEPROM_AddFile failed" && i == 11);
     assert("Check EEPROM_AddFile return error" && err == NOTENOUGHSPACE);
+
+    // Added files are linked into one chain and kept after the failed add
+    char fileList[16][FILE_NAME_LENGTH];
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check EEPROM_ListFiles after full EEPROM" && err == 11);
+    for (i = 0; i < 11; i++) {
+        sprintf(filename, "%s_%d", TEST_FILENAME, i);
+        assert("Check file list order" && strncmp(fileList[i], filename, FILE_NAME_LENGTH) == 0);
+        assert("Compare added file" && compare_file(ep, filename, (const uint8_t *) test_files[i], strlen(test_files[i]) + 1));
+    }
     EEPROM_CloseEEPROM(ep);
 
 }
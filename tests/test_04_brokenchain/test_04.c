@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: (GPL-2.0+ or MIT)
+/*
+ * Copyright (c) 2023 JetHome. All rights reserved.
+ * Author: Viacheslav Bocharov <adeep@lexina.in>
+ */
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+#include <libgen.h>
+
+#define DEBUG 1
+
+#include "jeefs.h"
+#include "tests-common.h"
+#include "debug.h"
+#include "eepromerr.h"
+
+void test4(void);
+
+int main() {
+    printf("Test 04! DEBUG:%i\n",DEBUG);
+    // print sizes of structures from jeefs.h
+    printf("sizeof(JEEPROMHeader) = %lu\n", sizeof(JEEPROMHeader));
+    printf("sizeof(JEEFSFileHeader) = %lu\n", sizeof(JEEFSFileHeader));
+
+    test4();
+
+    printf("+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n Test 4 - passed\n+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n");
+    return 0;
+}
+
+void test4(void) {
+    EEPROMDescriptor ep = EEPROM_OpenEEPROM(TEST_FULL_EEPROM_FILENAME, 0);
+    assert(("Check eeprom_open result", ep.eeprom_fid > 0));
+    assert(("Check eeprom_open result size = 8192", ep.eeprom_size == TEST_EEPROM_SIZE));
+    printf("EEPROM opened, size: %lu\n", ep.eeprom_size);
+
+    EEPROM_FormatEEPROM(ep);
+
+    char fileList[16][FILE_NAME_LENGTH];
+    uint8_t filedata[64];
+    uint16_t first = sizeof(JEEPROMHeader);
+    uint16_t second = first + sizeof(JEEFSFileHeader) + 16;
+    int err;
+
+    // Cycle: second file links back to the first one
+    write_raw_file(ep, first, "first", NULL, 16, second);
+    write_raw_file(ep, second, "second", NULL, 16, first);
+    err = EEPROM_ReadFile(ep, "missing", filedata, sizeof(filedata));
+    assert("Check lookup stops on cyclic chain" && err == FILENOTFOUND);
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    printf("Cyclic chain: %i files listed\n", err);
+    assert("Check list stops on cyclic chain" && err == 2);
+
+    // Link overlapping the data of the current file
+    write_raw_file(ep, second, "second", NULL, 16, second + 4);
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check list stops on overlapping link" && err == 2);
+
+    // Link pointing past the end of eeprom
+    write_raw_file(ep, second, "second", NULL, 16, ep.eeprom_size - sizeof(JEEFSFileHeader) + 1);
+    err = EEPROM_ReadFile(ep, "missing", filedata, sizeof(filedata));
+    assert("Check lookup stops on out of bounds link" && err == FILENOTFOUND);
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check list stops on out of bounds link" && err == 2);
+
+    // Link leaving a gap after the current file data, files can't be fragmented
+    write_raw_file(ep, first, "first", NULL, 16, second + 8);
+    write_raw_file(ep, second + 8, "second", NULL, 16, 0);
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check list stops on gapped link" && err == 1);
+    err = EEPROM_ReadFile(ep, "second", filedata, sizeof(filedata));
+    assert("Check lookup stops on gapped link" && err == FILENOTFOUND);
+
+    // Writers must not append over files behind the broken link
+    uint8_t rawBefore[TEST_EEPROM_SIZE], rawAfter[TEST_EEPROM_SIZE];
+    assert(eeprom_read(ep, rawBefore, sizeof(rawBefore), 0) == sizeof(rawBefore));
+    err = EEPROM_AddFile(ep, "third", filedata, 16);
+    assert("Check add on gapped link" && err == EEPROMCORRUPTED);
+    err = EEPROM_WriteFile(ep, "first", filedata, 32);
+    assert("Check resize on gapped link" && err == EEPROMCORRUPTED);
+    err = EEPROM_DeleteFile(ep, "first");
+    assert("Check delete on gapped link" && err == EEPROMCORRUPTED);
+    assert(eeprom_read(ep, rawAfter, sizeof(rawAfter), 0) == sizeof(rawAfter));
+    assert("Check gapped chain is kept" && memcmp(rawBefore, rawAfter, sizeof(rawBefore)) == 0);
+
+    // File data extends past the end of eeprom
+    static uint8_t bigdata[8100];
+    EEPROM_FormatEEPROM(ep);
+    write_raw_file(ep, first, "first", NULL, sizeof(bigdata), 0);
+    err = EEPROM_ReadFile(ep, "first", bigdata, sizeof(bigdata));
+    assert("Check read of out of bounds file" && err == EEPROMCORRUPTED);
+    err = EEPROM_WriteFile(ep, "first", bigdata, sizeof(bigdata));
+    assert("Check write of out of bounds file" && err == EEPROMCORRUPTED);
+    err = EEPROM_DeleteFile(ep, "first");
+    assert("Check delete of out of bounds file" && err == EEPROMCORRUPTED);
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check out of bounds file is kept" && err == 1 && strcmp(fileList[0], "first") == 0);
+
+    EEPROM_CloseEEPROM(ep);
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: (GPL-2.0+ or MIT)
+/*
+ * Copyright (c) 2023 JetHome. All rights reserved.
+ * Author: Viacheslav Bocharov <adeep@lexina.in>
+ */
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+#include <libgen.h>
+
+#define DEBUG 1
+
+#include "jeefs.h"
+#include "tests-common.h"
+#include "debug.h"
+#include "eepromerr.h"
+
+void test5(void);
+
+int main() {
+    printf("Test 05! DEBUG:%i\n",DEBUG);
+    // print sizes of structures from jeefs.h
+    printf("sizeof(JEEPROMHeader) = %lu\n", sizeof(JEEPROMHeader));
+    printf("sizeof(JEEFSFileHeader) = %lu\n", sizeof(JEEFSFileHeader));
+
+    test5();
+
+    printf("+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n Test 5 - passed\n+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n");
+    return 0;
+}
+
+void test5(void) {
+    EEPROMDescriptor ep = EEPROM_OpenEEPROM(TEST_FULL_EEPROM_FILENAME, 0);
+    assert(("Check eeprom_open result", ep.eeprom_fid > 0));
+    assert(("Check eeprom_open result size = 8192", ep.eeprom_size == TEST_EEPROM_SIZE));
+    printf("EEPROM opened, size: %lu\n", ep.eeprom_size);
+
+    EEPROM_FormatEEPROM(ep);
+
+    uint8_t filedata[16] = "filedata";
+    int err;
+
+    err = EEPROM_AddFile(ep, NULL, filedata, sizeof(filedata));
+    assert("Check NULL filename" && err == FILENAMENOTVALID);
+
+    err = EEPROM_AddFile(ep, "", filedata, sizeof(filedata));
+    assert("Check empty filename" && err == FILENAMETOOSHORT);
+    err = EEPROM_ReadFile(ep, "", filedata, sizeof(filedata));
+    assert("Check empty filename on read" && err == FILENAMETOOSHORT);
+
+    err = EEPROM_AddFile(ep, "0123456789abcdef", filedata, sizeof(filedata));
+    assert("Check too long filename" && err == FILENAMETOOLONG);
+    err = EEPROM_DeleteFile(ep, "0123456789abcdef");
+    assert("Check too long filename on delete" && err == FILENAMETOOLONG);
+
+    err = EEPROM_AddFile(ep, "bad\nname", filedata, sizeof(filedata));
+    assert("Check control character in filename" && err == FILENAMENOTVALID);
+    err = EEPROM_AddFile(ep, "bad\xffname", filedata, sizeof(filedata));
+    assert("Check non ASCII character in filename" && err == FILENAMENOTVALID);
+
+    err = EEPROM_AddFile(ep, ".hidden", filedata, sizeof(filedata));
+    assert("Check reserved dot prefix" && err == FILENAMENOTVALID);
+    err = EEPROM_AddFile(ep, "factory/mac", filedata, sizeof(filedata));
+    assert("Check reserved factory prefix" && err == FILENAMENOTVALID);
+
+    // Caller defined reserved prefixes replace the default ones
+    static const char *const customPrefixes[] = {"tmp/"};
+    EEPROM_SetReservedNamePrefixes(customPrefixes, 1);
+    err = EEPROM_AddFile(ep, "tmp/file", filedata, sizeof(filedata));
+    assert("Check custom reserved prefix" && err == FILENAMENOTVALID);
+    err = EEPROM_AddFile(ep, ".hidden", filedata, sizeof(filedata));
+    assert("Check default prefix allowed by custom list" && err == sizeof(filedata));
+    err = EEPROM_DeleteFile(ep, ".hidden");
+    assert("Check delete of file with default prefix" && err == 1);
+    EEPROM_SetReservedNamePrefixes(NULL, 0);
+    err = EEPROM_AddFile(ep, ".hidden", filedata, sizeof(filedata));
+    assert("Check default reserved prefixes restored" && err == FILENAMENOTVALID);
+
+    err = EEPROM_ReadFile(ep, "0123456789abcde", filedata, sizeof(filedata));
+    assert("Check max length filename is valid" && err == FILENOTFOUND);
+
+    // File created with non ASCII name before name rules must stay accessible
+    const char *legacyName = "\xd1\x84\xd0\xb0\xd0\xb9\xd0\xbb"; // UTF-8 name, 8 bytes
+    uint8_t legacydata[32] = "legacy file data";
+    write_raw_file(ep, sizeof(JEEPROMHeader), legacyName, legacydata, sizeof(legacydata), 0);
+    assert("Check read of legacy name" && compare_file(ep, legacyName, legacydata, sizeof(legacydata)));
+
+    legacydata[0] = 'L';
+    err = EEPROM_WriteFile(ep, legacyName, legacydata, sizeof(legacydata));
+    assert("Check overwrite of legacy name" && err == sizeof(legacydata));
+    err = EEPROM_WriteFile(ep, legacyName, filedata, sizeof(filedata));
+    assert("Check resize of legacy name" && err == sizeof(filedata));
+    assert("Compare resized legacy file" && compare_file(ep, legacyName, filedata, sizeof(filedata)));
+
+    err = EEPROM_DeleteFile(ep, legacyName);
+    assert("Check delete of legacy name" && err == 1);
+    err = EEPROM_ReadFile(ep, legacyName, filedata, sizeof(filedata));
+    assert("Check legacy name deleted" && err == FILENOTFOUND);
+
+    EEPROM_CloseEEPROM(ep);
+}
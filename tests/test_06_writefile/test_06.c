@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: (GPL-2.0+ or MIT)
+/*
+ * Copyright (c) 2023 JetHome. All rights reserved.
+ * Author: Viacheslav Bocharov <adeep@lexina.in>
+ */
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+#include <libgen.h>
+
+#define DEBUG 1
+
+#include "jeefs.h"
+#include "tests-common.h"
+#include "debug.h"
+#include "eepromerr.h"
+
+void test6(void);
+
+int main() {
+    printf("Test 06! DEBUG:%i\n",DEBUG);
+    // print sizes of structures from jeefs.h
+    printf("sizeof(JEEPROMHeader) = %lu\n", sizeof(JEEPROMHeader));
+    printf("sizeof(JEEFSFileHeader) = %lu\n", sizeof(JEEFSFileHeader));
+
+    test6();
+
+    printf("+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n Test 6 - passed\n+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n");
+    return 0;
+}
+
+void test6(void) {
+    EEPROMDescriptor ep = EEPROM_OpenEEPROM(TEST_FULL_EEPROM_FILENAME, 0);
+    assert(("Check eeprom_open result", ep.eeprom_fid > 0));
+    assert(("Check eeprom_open result size = 8192", ep.eeprom_size == TEST_EEPROM_SIZE));
+    printf("EEPROM opened, size: %lu\n", ep.eeprom_size);
+
+    EEPROM_FormatEEPROM(ep);
+
+    uint8_t filedata[8192];
+    uint8_t smalldata[16] = "small file data";
+    int err;
+
+    // Fill eeprom so that only 20 bytes remain after the last file
+    uint16_t second = ep.eeprom_size - 20 - sizeof(JEEFSFileHeader) - sizeof(smalldata);
+    uint16_t first = sizeof(JEEPROMHeader);
+    memset(filedata, 'x', sizeof(filedata));
+    write_raw_file(ep, first, "big", filedata, second - first - sizeof(JEEFSFileHeader), second);
+    write_raw_file(ep, second, "small", smalldata, sizeof(smalldata), 0);
+
+    // Grow small file past the end of eeprom
+    err = EEPROM_WriteFile(ep, "small", filedata, sizeof(smalldata) + 20);
+    assert("Check EEPROM_WriteFile reports no space" && err == NOTENOUGHSPACE);
+
+    // Existing file must be kept intact
+    memset(filedata, 0, sizeof(filedata));
+    err = EEPROM_ReadFile(ep, "small", filedata, sizeof(filedata));
+    assert("Check file kept after failed resize" && err == sizeof(smalldata));
+    assert("Compare kept file with original" && memcmp(filedata, smalldata, sizeof(smalldata)) == 0);
+
+    // Resize file in the middle of the chain, files behind it must stay readable
+    EEPROM_FormatEEPROM(ep);
+    char filename[100];
+    for (int i = 0; i < 4; i++) {
+        sprintf(filename, "%s_%d", TEST_FILENAME, i);
+        err = EEPROM_AddFile(ep, filename, (const uint8_t *) test_files[i], strlen(test_files[i]) + 1);
+        assert("Check EEPROM_AddFile result" && err == strlen(test_files[i]) + 1);
+    }
+
+    // Same size, overwrite in place
+    memcpy(filedata, test_files[1], strlen(test_files[1]) + 1);
+    filedata[0] = 'h';
+    err = EEPROM_WriteFile(ep, TEST_FILENAME "_1", filedata, strlen(test_files[1]) + 1);
+    assert("Check same size EEPROM_WriteFile result" && err == strlen(test_files[1]) + 1);
+    assert("Compare overwritten file" && compare_file(ep, TEST_FILENAME "_1", filedata, strlen(test_files[1]) + 1));
+
+    // Shrink
+    err = EEPROM_WriteFile(ep, TEST_FILENAME "_1", smalldata, sizeof(smalldata));
+    assert("Check shrink EEPROM_WriteFile result" && err == sizeof(smalldata));
+    assert("Compare shrunk file" && compare_file(ep, TEST_FILENAME "_1", smalldata, sizeof(smalldata)));
+    assert("Compare file before resized one" && compare_file(ep, TEST_FILENAME "_0", (const uint8_t *) test_files[0], strlen(test_files[0]) + 1));
+    assert("Compare file after resized one" && compare_file(ep, TEST_FILENAME "_2", (const uint8_t *) test_files[2], strlen(test_files[2]) + 1));
+    assert("Compare last file" && compare_file(ep, TEST_FILENAME "_3", (const uint8_t *) test_files[3], strlen(test_files[3]) + 1));
+
+    // Grow, resized file is moved to the end of the chain
+    memcpy(filedata, test_files[4], strlen(test_files[4]) + 1);
+    err = EEPROM_WriteFile(ep, TEST_FILENAME "_1", filedata, strlen(test_files[4]) + 1);
+    assert("Check grow EEPROM_WriteFile result" && err == strlen(test_files[4]) + 1);
+    assert("Compare grown file" && compare_file(ep, TEST_FILENAME "_1", filedata, strlen(test_files[4]) + 1));
+    for (int i = 0; i < 4; i++) {
+        if (i == 1)
+            continue;
+        sprintf(filename, "%s_%d", TEST_FILENAME, i);
+        assert("Compare files after grow" && compare_file(ep, filename, (const uint8_t *) test_files[i], strlen(test_files[i]) + 1));
+    }
+    char fileList[16][FILE_NAME_LENGTH];
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check file list after grow" && err == 4 && strcmp(fileList[3], TEST_FILENAME "_1") == 0);
+
+    EEPROM_CloseEEPROM(ep);
+}
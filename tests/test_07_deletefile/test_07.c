@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: (GPL-2.0+ or MIT)
+/*
+ * Copyright (c) 2023 JetHome. All rights reserved.
+ * Author: Viacheslav Bocharov <adeep@lexina.in>
+ */
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+#include <libgen.h>
+
+#define DEBUG 1
+
+#include "jeefs.h"
+#include "tests-common.h"
+#include "debug.h"
+#include "eepromerr.h"
+
+void test7(void);
+
+int main() {
+    printf("Test 07! DEBUG:%i\n",DEBUG);
+    // print sizes of structures from jeefs.h
+    printf("sizeof(JEEPROMHeader) = %lu\n", sizeof(JEEPROMHeader));
+    printf("sizeof(JEEFSFileHeader) = %lu\n", sizeof(JEEFSFileHeader));
+
+    test7();
+
+    printf("+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n Test 7 - passed\n+++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++\n");
+    return 0;
+}
+
+// Check that file exists and its content matches test_files[index]
+static void check_file(EEPROMDescriptor ep, const char *name, int index) {
+    uint16_t filesize = strlen(test_files[index]) + 1;
+    assert("Compare file with original" && compare_file(ep, name, (const uint8_t *) test_files[index], filesize));
+}
+
+static void add_file(EEPROMDescriptor ep, const char *name, int index) {
+    uint16_t filesize = strlen(test_files[index]) + 1;
+    int err = EEPROM_AddFile(ep, name, (const uint8_t *) test_files[index], filesize);
+    printf("Add file %s: %i\n", name, err);
+    assert("Check EEPROM_AddFile result" && err == filesize);
+}
+
+void test7(void) {
+    EEPROMDescriptor ep = EEPROM_OpenEEPROM(TEST_FULL_EEPROM_FILENAME, 0);
+    assert(("Check eeprom_open result", ep.eeprom_fid > 0));
+    assert(("Check eeprom_open result size = 8192", ep.eeprom_size == TEST_EEPROM_SIZE));
+    printf("EEPROM opened, size: %lu\n", ep.eeprom_size);
+
+    EEPROM_FormatEEPROM(ep);
+
+    char fileList[16][FILE_NAME_LENGTH];
+    int err;
+
+    add_file(ep, "a", 0);
+    add_file(ep, "b", 1);
+    add_file(ep, "c", 2);
+    add_file(ep, "d", 3);
+
+    // Delete first file, following files are moved and relinked
+    err = EEPROM_DeleteFile(ep, "a");
+    assert("Check delete first file" && err == 1);
+    err = EEPROM_ReadFile(ep, "a", (uint8_t *) fileList, sizeof(fileList));
+    assert("Check deleted file not found" && err == FILENOTFOUND);
+    check_file(ep, "b", 1);
+    check_file(ep, "c", 2);
+    check_file(ep, "d", 3);
+
+    // New file is appended after the moved files
+    add_file(ep, "e", 4);
+    check_file(ep, "b", 1);
+    check_file(ep, "c", 2);
+    check_file(ep, "d", 3);
+    check_file(ep, "e", 4);
+
+    // Delete file in the middle
+    err = EEPROM_DeleteFile(ep, "c");
+    assert("Check delete middle file" && err == 1);
+    check_file(ep, "b", 1);
+    check_file(ep, "d", 3);
+    check_file(ep, "e", 4);
+
+    // Delete last file, chain is terminated at previous file
+    err = EEPROM_DeleteFile(ep, "e");
+    assert("Check delete last file" && err == 1);
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check file list after delete" && err == 2);
+    assert("Check file list names" && strcmp(fileList[0], "b") == 0 && strcmp(fileList[1], "d") == 0);
+
+    add_file(ep, "f", 5);
+    check_file(ep, "b", 1);
+    check_file(ep, "d", 3);
+    check_file(ep, "f", 5);
+    err = EEPROM_ListFiles(ep, fileList, 16);
+    assert("Check file list after add" && err == 3);
+
+    EEPROM_CloseEEPROM(ep);
+}